@@ -0,0 +1,55 @@
+//! The syntax tree produced by the `parser` module.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identifier {
+    pub value: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LetStatement {
+    pub name: Identifier,
+    pub value: Expression,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReturnStatement {
+    pub return_value: Expression,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExpressionStatement {
+    pub expression: Expression,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PrefixExpression {
+    pub operator: String,
+    pub right: Box<Expression>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InfixExpression {
+    pub left: Box<Expression>,
+    pub operator: String,
+    pub right: Box<Expression>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Expression {
+    Identifier(Identifier),
+    IntegerLiteral(i64),
+    Prefix(PrefixExpression),
+    Infix(InfixExpression),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Statement {
+    Let(LetStatement),
+    Return(ReturnStatement),
+    Expression(ExpressionStatement),
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Program {
+    pub statements: Vec<Statement>,
+}
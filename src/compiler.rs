@@ -0,0 +1,238 @@
+//! Walks the `ast::Program` produced by the `parser` module into a `Chunk`
+//! of bytecode that the `vm` module can execute.
+
+use std::fmt::Display;
+
+use crate::ast::{Expression, Program, Statement};
+use crate::lexer::Span;
+use crate::value::Value;
+
+/// A bytecode instruction. Discriminants are the opcode bytes stored in
+/// `Chunk::code`, decoded by `Instruction::from_byte`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Constant = 0,
+    Return = 1,
+    Pop,
+    Negate,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Instruction {
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Instruction::Constant),
+            1 => Some(Instruction::Return),
+            2 => Some(Instruction::Pop),
+            3 => Some(Instruction::Negate),
+            4 => Some(Instruction::Add),
+            5 => Some(Instruction::Subtract),
+            6 => Some(Instruction::Multiply),
+            7 => Some(Instruction::Divide),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileErrorKind {
+    /// An AST node this compiler doesn't know how to lower yet, e.g. a bare
+    /// identifier reference (there is no variable environment in the VM).
+    UnsupportedExpression(String),
+    UnsupportedOperator(String),
+    TooManyConstants,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileError {
+    pub kind: CompileErrorKind,
+    pub span: Span,
+}
+
+impl CompileError {
+    fn new(kind: CompileErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            CompileErrorKind::UnsupportedExpression(what) => {
+                write!(f, "cannot compile {} yet", what)
+            }
+            CompileErrorKind::UnsupportedOperator(op) => {
+                write!(f, "unsupported operator `{}`", op)
+            }
+            CompileErrorKind::TooManyConstants => {
+                write!(f, "chunk has more than {} constants", u8::MAX)
+            }
+        }
+    }
+}
+
+/// A compiled unit: a flat byte-per-instruction `code` stream paired with
+/// the `Span` each byte came from, and the pool of literal `constants` the
+/// `Constant` instruction indexes into.
+#[derive(Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<(u8, Span)>,
+    pub constants: Vec<Value>,
+}
+
+impl Chunk {
+    fn push_byte(&mut self, byte: u8, span: Span) {
+        self.code.push((byte, span));
+    }
+
+    fn add_constant(&mut self, value: Value) -> Result<u8, CompileErrorKind> {
+        if self.constants.len() >= u8::MAX as usize {
+            return Err(CompileErrorKind::TooManyConstants);
+        }
+
+        self.constants.push(value);
+        Ok((self.constants.len() - 1) as u8)
+    }
+}
+
+/// Compiles `program` into a `Chunk`.
+///
+/// There is no variable environment in the VM yet, so a `let` statement's
+/// value is compiled and discarded rather than bound; only the final
+/// statement's value (if it's an expression) is left for `Instruction::Return`
+/// to hand back to the caller.
+pub fn compile(program: &Program) -> Result<Chunk, CompileError> {
+    let mut chunk = Chunk::default();
+    let last_index = program.statements.len().checked_sub(1);
+
+    for (i, statement) in program.statements.iter().enumerate() {
+        match statement {
+            Statement::Expression(stmt) => {
+                compile_expression(&stmt.expression, &mut chunk)?;
+                let instruction = if Some(i) == last_index {
+                    Instruction::Return
+                } else {
+                    Instruction::Pop
+                };
+                chunk.push_byte(instruction as u8, Span::default());
+            }
+            Statement::Let(stmt) => {
+                compile_expression(&stmt.value, &mut chunk)?;
+                chunk.push_byte(Instruction::Pop as u8, Span::default());
+            }
+            Statement::Return(stmt) => {
+                compile_expression(&stmt.return_value, &mut chunk)?;
+                chunk.push_byte(Instruction::Return as u8, Span::default());
+            }
+        }
+    }
+
+    Ok(chunk)
+}
+
+fn compile_expression(expression: &Expression, chunk: &mut Chunk) -> Result<(), CompileError> {
+    match expression {
+        Expression::IntegerLiteral(value) => {
+            let index = chunk
+                .add_constant(Value::Int(*value))
+                .map_err(|kind| CompileError::new(kind, Span::default()))?;
+            chunk.push_byte(Instruction::Constant as u8, Span::default());
+            chunk.push_byte(index, Span::default());
+        }
+        Expression::Prefix(expr) => {
+            compile_expression(&expr.right, chunk)?;
+            match expr.operator.as_str() {
+                "-" => chunk.push_byte(Instruction::Negate as u8, Span::default()),
+                _ => {
+                    return Err(CompileError::new(
+                        CompileErrorKind::UnsupportedOperator(expr.operator.clone()),
+                        Span::default(),
+                    ))
+                }
+            }
+        }
+        Expression::Infix(expr) => {
+            compile_expression(&expr.left, chunk)?;
+            compile_expression(&expr.right, chunk)?;
+            let instruction = match expr.operator.as_str() {
+                "+" => Instruction::Add,
+                "-" => Instruction::Subtract,
+                "*" => Instruction::Multiply,
+                "/" => Instruction::Divide,
+                _ => {
+                    return Err(CompileError::new(
+                        CompileErrorKind::UnsupportedOperator(expr.operator.clone()),
+                        Span::default(),
+                    ))
+                }
+            };
+            chunk.push_byte(instruction as u8, Span::default());
+        }
+        Expression::Identifier(ident) => {
+            return Err(CompileError::new(
+                CompileErrorKind::UnsupportedExpression(format!("identifier `{}`", ident.value)),
+                Span::default(),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+
+    fn compile_source(input: &str) -> Chunk {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert_eq!(parser.errors(), &[], "parser had errors: {:?}", parser.errors());
+        compile(&program).expect("compile failed")
+    }
+
+    #[test]
+    fn test_compile_integer_literal() {
+        let chunk = compile_source("5;");
+        assert_eq!(chunk.constants, vec![Value::Int(5)]);
+        let ops: Vec<u8> = chunk.code.iter().map(|(b, _)| *b).collect();
+        assert_eq!(ops, vec![Instruction::Constant as u8, 0, Instruction::Return as u8]);
+    }
+
+    #[test]
+    fn test_compile_infix_expression() {
+        let chunk = compile_source("1 + 2;");
+        assert_eq!(chunk.constants, vec![Value::Int(1), Value::Int(2)]);
+        let ops: Vec<u8> = chunk.code.iter().map(|(b, _)| *b).collect();
+        assert_eq!(
+            ops,
+            vec![
+                Instruction::Constant as u8,
+                0,
+                Instruction::Constant as u8,
+                1,
+                Instruction::Add as u8,
+                Instruction::Return as u8,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_identifier_is_unsupported() {
+        let lexer = Lexer::new("foobar;");
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert_eq!(parser.errors(), &[]);
+
+        let err = compile(&program).unwrap_err();
+        assert_eq!(
+            err.kind,
+            CompileErrorKind::UnsupportedExpression("identifier `foobar`".to_owned())
+        );
+    }
+}
@@ -1,180 +1,420 @@
 use std::fmt::Display;
 
+/// A byte-offset range into the source, plus the line/column the range starts at.
+///
+/// Lines and columns are both 1-indexed, matching how editors report positions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub(crate) fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// A zero-width span at a bare byte offset, for callers (like `Parser`)
+    /// that only have a `LexError`'s position to report and no line/column.
+    pub(crate) fn at(pos: usize) -> Self {
+        Self {
+            start: pos,
+            end: pos,
+            line: 0,
+            column: 0,
+        }
+    }
+}
+
+/// The kind of failure that stopped the lexer from producing a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LexErrorKind {
+    UnexpectedCharacter(char),
+    UnterminatedString,
+    InvalidNumber,
+}
+
+/// A lex failure, with the byte offset into the source it occurred at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub pos: usize,
+}
+
+impl LexError {
+    fn new(kind: LexErrorKind, pos: usize) -> Self {
+        Self { kind, pos }
+    }
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            LexErrorKind::UnexpectedCharacter(ch) => {
+                write!(f, "unexpected character '{}' at byte {}", ch, self.pos)
+            }
+            LexErrorKind::UnterminatedString => {
+                write!(f, "unterminated string literal starting at byte {}", self.pos)
+            }
+            LexErrorKind::InvalidNumber => {
+                write!(f, "invalid number literal at byte {}", self.pos)
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
-pub enum Token {
-    ILLEGAL,
-    EOF,
+pub enum Token<'src> {
+    Illegal,
+    Eof,
     // Identifiers + literals
-    IDENT(String),
-    INT(String),
-    ASSIGN,
-    PLUS,
-    MINUS,
-    BANG,
-    ASTERISK,
-    SLASH,
-    LT,
-    GT,
-    EQUAL,
-    NOTEQUAL,
+    Ident(&'src str),
+    Int(&'src str),
+    Float(&'src str),
+    String(String),
+    Assign,
+    Plus,
+    Minus,
+    Bang,
+    Asterisk,
+    Slash,
+    Lt,
+    Gt,
+    Equal,
+    NotEqual,
     // Delimiters
-    COMMA,
-    SEMICOLON,
-    LPAREN,
-    RPAREN,
-    LBRACE,
-    RBRACE,
+    Comma,
+    Semicolon,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
     // Keywords
-    FUNCTION,
-    LET,
-    TRUE,
-    FALSE,
-    IF,
-    ELSE,
-    RETURN,
+    Function,
+    Let,
+    True,
+    False,
+    If,
+    Else,
+    Return,
+}
+
+impl Token<'_> {
+    /// The literal source text for an operator token, e.g. `Token::Bang` ->
+    /// `"!"`. Used by the parser to build AST operator strings from the
+    /// actual lexeme rather than the `Display` variant name.
+    pub(crate) fn operator_lexeme(&self) -> Option<&'static str> {
+        match self {
+            Token::Plus => Some("+"),
+            Token::Minus => Some("-"),
+            Token::Bang => Some("!"),
+            Token::Asterisk => Some("*"),
+            Token::Slash => Some("/"),
+            Token::Lt => Some("<"),
+            Token::Gt => Some(">"),
+            Token::Equal => Some("=="),
+            Token::NotEqual => Some("!="),
+            _ => None,
+        }
+    }
 }
 
-impl Display for Token {
+impl Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Token::IDENT(x) => write!(f, "Ident({})", x),
-            Token::INT(x) => write!(f, "Int({})", x),
-            Token::ILLEGAL => write!(f, "Illegal"),
-            Token::EOF => write!(f, "Eof"),
-            Token::ASSIGN => write!(f, "Assign"),
-            Token::PLUS => write!(f, "Plus"),
-            Token::COMMA => write!(f, "Comma"),
-            Token::SEMICOLON => write!(f, "Semicolon"),
-            Token::LPAREN => write!(f, "Lparen"),
-            Token::RPAREN => write!(f, "Rparen"),
-            Token::LBRACE => write!(f, "LSquirly"),
-            Token::RBRACE => write!(f, "RSquirly"),
-            Token::FUNCTION => write!(f, "Function"),
-            Token::LET => write!(f, "Let"),
-            Token::MINUS => write!(f, "Minus"),
-            Token::BANG => write!(f, "Bang"),
-            Token::ASTERISK => write!(f, "Asteriks"),
-            Token::SLASH => write!(f, "Slash"),
-            Token::LT => writeln!(f, "LessThan"),
-            Token::GT => write!(f, "GreaterThan"),
-            Token::TRUE => write!(f, "True"),
-            Token::FALSE => write!(f, "False"),
-            Token::IF => write!(f, "If"),
-            Token::ELSE => write!(f, "Else"),
-            Token::RETURN => write!(f, "Return"),
-            Token::EQUAL => write!(f, "Equal"),
-            Token::NOTEQUAL => write!(f, "NotEqual"),
+            Token::Ident(x) => write!(f, "Ident({})", x),
+            Token::Int(x) => write!(f, "Int({})", x),
+            Token::Float(x) => write!(f, "Float({})", x),
+            Token::String(x) => write!(f, "String({})", x),
+            Token::Illegal => write!(f, "Illegal"),
+            Token::Eof => write!(f, "Eof"),
+            Token::Assign => write!(f, "Assign"),
+            Token::Plus => write!(f, "Plus"),
+            Token::Comma => write!(f, "Comma"),
+            Token::Semicolon => write!(f, "Semicolon"),
+            Token::LParen => write!(f, "LParen"),
+            Token::RParen => write!(f, "RParen"),
+            Token::LBrace => write!(f, "LSquirly"),
+            Token::RBrace => write!(f, "RSquirly"),
+            Token::Function => write!(f, "Function"),
+            Token::Let => write!(f, "Let"),
+            Token::Minus => write!(f, "Minus"),
+            Token::Bang => write!(f, "Bang"),
+            Token::Asterisk => write!(f, "Asterisk"),
+            Token::Slash => write!(f, "Slash"),
+            Token::Lt => write!(f, "LessThan"),
+            Token::Gt => write!(f, "GreaterThan"),
+            Token::True => write!(f, "True"),
+            Token::False => write!(f, "False"),
+            Token::If => write!(f, "If"),
+            Token::Else => write!(f, "Else"),
+            Token::Return => write!(f, "Return"),
+            Token::Equal => write!(f, "Equal"),
+            Token::NotEqual => write!(f, "NotEqual"),
         }
     }
 }
 
 #[derive(Debug, Default, Clone)]
-struct Lexer {
-    input: Vec<u8>,
+pub struct Lexer<'src> {
+    input: &'src str,
     pos: usize,
     read_pos: usize,
     ch: u8,
+    line: usize,
+    column: usize,
+    eof_sent: bool,
+    comments: Vec<(Span, &'src str)>,
 }
 
-impl Lexer {
-    fn new(input: String) -> Self {
+impl<'src> Lexer<'src> {
+    pub fn new(input: &'src str) -> Self {
         let mut s = Self {
-            input: input.into_bytes(),
+            input,
             read_pos: 0,
             ch: 0,
             pos: 0,
+            line: 1,
+            column: 0,
+            eof_sent: false,
+            comments: Vec::new(),
         };
 
         s.read_char();
         s
     }
 
+    /// Line and block comments skipped so far, in source order, for a future
+    /// formatter that wants to reattach them.
+    pub fn comments(&self) -> &[(Span, &'src str)] {
+        &self.comments
+    }
+
     fn read_char(&mut self) {
-        if self.read_pos >= self.input.len() {
+        if self.ch == b'\n' {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+
+        let bytes = self.input.as_bytes();
+        if self.read_pos >= bytes.len() {
             self.ch = Default::default();
         } else {
-            self.ch = self.input[self.read_pos];
+            self.ch = bytes[self.read_pos];
         }
 
         self.pos = self.read_pos;
         self.read_pos += 1;
     }
 
-    fn next_token(&mut self) -> Token {
-        self.skip_whitespace();
+    pub fn next_token(&mut self) -> Result<(Token<'src>, Span), LexError> {
+        loop {
+            self.skip_whitespace();
+
+            if self.ch == b'/' && self.peek_char() == b'/' {
+                self.skip_line_comment();
+                continue;
+            }
+
+            if self.ch == b'/' && self.peek_char() == b'*' {
+                self.skip_block_comment();
+                continue;
+            }
+
+            break;
+        }
+
+        let start_pos = self.pos;
+        let start_line = self.line;
+        let start_column = self.column;
+
         let tok = match self.ch {
             b'=' => {
                 if self.peek_char() == b'=' {
                     self.read_char();
-                    Token::EQUAL // return out of the function
+                    Token::Equal // return out of the function
                 } else {
-                    Token::ASSIGN
+                    Token::Assign
                 }
             }
             b'!' => {
                 if self.peek_char() == b'=' {
                     self.read_char();
-                    Token::NOTEQUAL // return out of the function
+                    Token::NotEqual // return out of the function
                 } else {
-                    Token::BANG
+                    Token::Bang
                 }
             }
-            b'{' => Token::LBRACE,
-            b'}' => Token::RBRACE,
-            b'(' => Token::LPAREN,
-            b')' => Token::RPAREN,
-            b',' => Token::COMMA,
-            b';' => Token::SEMICOLON,
-            b'+' => Token::PLUS,
-            b'-' => Token::MINUS,
-            b'*' => Token::ASTERISK,
-            b'/' => Token::SLASH,
-            b'<' => Token::LT,
-            b'>' => Token::GT,
-            b'0'..=b'9' => return Token::INT(self.read_int()),
+            b'{' => Token::LBrace,
+            b'}' => Token::RBrace,
+            b'(' => Token::LParen,
+            b')' => Token::RParen,
+            b',' => Token::Comma,
+            b';' => Token::Semicolon,
+            b'+' => Token::Plus,
+            b'-' => Token::Minus,
+            b'*' => Token::Asterisk,
+            b'/' => Token::Slash,
+            b'<' => Token::Lt,
+            b'>' => Token::Gt,
+            b'"' => {
+                let s = self.read_string()?;
+                let span = Span::new(start_pos, self.pos, start_line, start_column);
+                return Ok((Token::String(s), span));
+            }
+            b'0'..=b'9' => {
+                let tok = self.read_number()?;
+                let span = Span::new(start_pos, self.pos, start_line, start_column);
+                return Ok((tok, span));
+            }
             b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
                 let ident = self.read_ident();
-                return match ident.as_str() {
-                    "fn" => Token::FUNCTION,
-                    "let" => Token::LET,
-                    "if" => Token::IF,
-                    "return" => Token::RETURN,
-                    "true" => Token::TRUE,
-                    "false" => Token::FALSE,
-                    "else" => Token::ELSE,
-                    _ => Token::IDENT(ident),
+                let tok = match ident {
+                    "fn" => Token::Function,
+                    "let" => Token::Let,
+                    "if" => Token::If,
+                    "return" => Token::Return,
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    "else" => Token::Else,
+                    _ => Token::Ident(ident),
                 };
+                let span = Span::new(start_pos, self.pos, start_line, start_column);
+                return Ok((tok, span));
+            }
+            0 => Token::Eof,
+            bad => {
+                let bad_char = bad as char;
+                self.read_char();
+                return Err(LexError::new(
+                    LexErrorKind::UnexpectedCharacter(bad_char),
+                    start_pos,
+                ));
             }
-            0 => Token::EOF,
-            _ => unreachable!(),
         };
 
         self.read_char();
-        tok
+        let span = Span::new(start_pos, self.pos, start_line, start_column);
+        Ok((tok, span))
     }
 
     fn peek_char(&self) -> u8 {
-        if self.read_pos >= self.input.len() {
-            return 0;
+        let bytes = self.input.as_bytes();
+        if self.read_pos >= bytes.len() {
+            0
         } else {
-            return self.input[self.read_pos];
+            bytes[self.read_pos]
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, LexError> {
+        let start_pos = self.pos;
+        let mut s = String::new();
+        self.read_char(); // consume opening quote
+
+        loop {
+            match self.ch {
+                b'"' => {
+                    self.read_char(); // consume closing quote
+                    return Ok(s);
+                }
+                0 => return Err(LexError::new(LexErrorKind::UnterminatedString, start_pos)),
+                b'\\' => {
+                    self.read_char();
+                    match self.ch {
+                        b'n' => s.push('\n'),
+                        b't' => s.push('\t'),
+                        b'"' => s.push('"'),
+                        b'\\' => s.push('\\'),
+                        0 => {
+                            return Err(LexError::new(LexErrorKind::UnterminatedString, start_pos))
+                        }
+                        other => {
+                            s.push('\\');
+                            s.push(other as char);
+                        }
+                    }
+                    self.read_char();
+                }
+                _ => {
+                    // Non-ASCII content is multi-byte UTF-8; copy the whole
+                    // character's bytes instead of widening one byte at a time.
+                    let char_start = self.pos;
+                    let char_len = utf8_char_len(self.ch);
+                    for _ in 0..char_len {
+                        self.read_char();
+                    }
+                    s.push_str(&self.input[char_start..self.pos]);
+                }
+            }
         }
     }
 
-    fn read_ident(&mut self) -> String {
+    fn read_ident(&mut self) -> &'src str {
         let current_pos = self.pos;
         while self.ch.is_ascii_alphabetic() || self.ch == b'_' {
             self.read_char();
         }
-        return String::from_utf8_lossy(&self.input[current_pos..self.pos]).to_string();
+        &self.input[current_pos..self.pos]
     }
 
-    fn read_int(&mut self) -> String {
-        let current_pos = self.pos;
-        while self.ch.is_ascii_digit() {
+    /// Reads an integer or float literal starting at the current byte, which is
+    /// guaranteed to be an ASCII digit. Handles `0x`/`0b` prefixes, `_` digit
+    /// separators, and a single `.` followed by digits for floats. The token
+    /// borrows its text directly from the source, `_` separators included.
+    fn read_number(&mut self) -> Result<Token<'src>, LexError> {
+        let start_pos = self.pos;
+
+        if self.ch == b'0' && matches!(self.peek_char(), b'x' | b'X' | b'b' | b'B') {
+            let is_hex = matches!(self.peek_char(), b'x' | b'X');
+            self.read_char(); // consume '0'
+            self.read_char(); // consume 'x'/'b'
+
+            let digits_start = self.pos;
+            while (is_hex && self.ch.is_ascii_hexdigit())
+                || (!is_hex && matches!(self.ch, b'0' | b'1'))
+                || self.ch == b'_'
+            {
+                self.read_char();
+            }
+
+            if self.pos == digits_start {
+                return Err(LexError::new(LexErrorKind::InvalidNumber, start_pos));
+            }
+
+            // Keep the `0x`/`0b` prefix in the token text so the parser can
+            // recover the radix; `parse_integer_literal` strips it back off.
+            return Ok(Token::Int(&self.input[start_pos..self.pos]));
+        }
+
+        while self.ch.is_ascii_digit() || self.ch == b'_' {
             self.read_char();
         }
-        return String::from_utf8_lossy(&self.input[current_pos..self.pos]).to_string();
+
+        if self.ch == b'.' {
+            if !self.peek_char().is_ascii_digit() {
+                self.read_char(); // consume the trailing '.' so lexing can continue
+                return Err(LexError::new(LexErrorKind::InvalidNumber, start_pos));
+            }
+
+            self.read_char(); // consume '.'
+            while self.ch.is_ascii_digit() || self.ch == b'_' {
+                self.read_char();
+            }
+
+            return Ok(Token::Float(&self.input[start_pos..self.pos]));
+        }
+
+        Ok(Token::Int(&self.input[start_pos..self.pos]))
     }
 
     fn skip_whitespace(&mut self) {
@@ -182,6 +422,86 @@ impl Lexer {
             self.read_char();
         }
     }
+
+    fn skip_line_comment(&mut self) {
+        let start_pos = self.pos;
+        let start_line = self.line;
+        let start_column = self.column;
+
+        while self.ch != 0 && self.ch != b'\n' {
+            self.read_char();
+        }
+
+        let text = &self.input[start_pos..self.pos];
+        self.comments.push((
+            Span::new(start_pos, self.pos, start_line, start_column),
+            text,
+        ));
+    }
+
+    fn skip_block_comment(&mut self) {
+        let start_pos = self.pos;
+        let start_line = self.line;
+        let start_column = self.column;
+
+        self.read_char(); // consume '/'
+        self.read_char(); // consume '*'
+
+        while self.ch != 0 && !(self.ch == b'*' && self.peek_char() == b'/') {
+            self.read_char();
+        }
+
+        if self.ch != 0 {
+            self.read_char(); // consume '*'
+            self.read_char(); // consume '/'
+        }
+
+        let text = &self.input[start_pos..self.pos];
+        self.comments.push((
+            Span::new(start_pos, self.pos, start_line, start_column),
+            text,
+        ));
+    }
+}
+
+impl<'src> Iterator for Lexer<'src> {
+    type Item = Result<(Token<'src>, Span), LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.eof_sent {
+            return None;
+        }
+
+        match self.next_token() {
+            Ok((tok, span)) => {
+                if tok == Token::Eof {
+                    self.eof_sent = true;
+                }
+                Some(Ok((tok, span)))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Lexes `input` in one shot, draining the `Lexer` iterator into a `Vec`.
+pub fn lex(input: &str) -> Vec<Result<(Token<'_>, Span), LexError>> {
+    Lexer::new(input).collect()
+}
+
+/// Number of UTF-8 bytes in the character that starts with `first_byte`,
+/// per the leading byte's high bits. `input` is always valid UTF-8, so this
+/// is exact, not a guess.
+fn utf8_char_len(first_byte: u8) -> usize {
+    if first_byte & 0x80 == 0 {
+        1
+    } else if first_byte & 0xE0 == 0xC0 {
+        2
+    } else if first_byte & 0xF0 == 0xE0 {
+        3
+    } else {
+        4
+    }
 }
 
 #[cfg(test)]
@@ -190,21 +510,21 @@ mod tests {
 
     #[test]
     fn test_next_token() {
-        let input: String = "=+(){},;".into();
+        let input = "=+(){},;";
         let expected_tokens = vec![
-            Token::ASSIGN,
-            Token::PLUS,
-            Token::LPAREN,
-            Token::RPAREN,
-            Token::LBRACE,
-            Token::RBRACE,
-            Token::COMMA,
-            Token::SEMICOLON,
+            Token::Assign,
+            Token::Plus,
+            Token::LParen,
+            Token::RParen,
+            Token::LBrace,
+            Token::RBrace,
+            Token::Comma,
+            Token::Semicolon,
         ];
         let mut lexer = Lexer::new(input);
 
         for expected_tok in expected_tokens {
-            let actual_tok = lexer.next_token();
+            let (actual_tok, _span) = lexer.next_token().unwrap();
             println!("actual: {:?}, expected: {:?}", actual_tok, expected_tok);
 
             assert_eq!(actual_tok, expected_tok);
@@ -223,48 +543,48 @@ mod tests {
         ";
 
         let expected_tokens = vec![
-            Token::LET,
-            Token::IDENT("five".to_owned()),
-            Token::ASSIGN,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("ten".to_owned()),
-            Token::ASSIGN,
-            Token::INT("10".to_owned()),
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("add".to_owned()),
-            Token::ASSIGN,
-            Token::FUNCTION,
-            Token::LPAREN,
-            Token::IDENT("x".to_owned()),
-            Token::COMMA,
-            Token::IDENT("y".to_owned()),
-            Token::RPAREN,
-            Token::LBRACE,
-            Token::IDENT("x".to_owned()),
-            Token::PLUS,
-            Token::IDENT("y".to_owned()),
-            Token::SEMICOLON,
-            Token::RBRACE,
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("result".to_owned()),
-            Token::ASSIGN,
-            Token::IDENT("add".to_owned()),
-            Token::LPAREN,
-            Token::IDENT("five".to_owned()),
-            Token::COMMA,
-            Token::IDENT("ten".to_owned()),
-            Token::RPAREN,
-            Token::SEMICOLON,
-            Token::EOF,
+            Token::Let,
+            Token::Ident("five"),
+            Token::Assign,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("ten"),
+            Token::Assign,
+            Token::Int("10"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("add"),
+            Token::Assign,
+            Token::Function,
+            Token::LParen,
+            Token::Ident("x"),
+            Token::Comma,
+            Token::Ident("y"),
+            Token::RParen,
+            Token::LBrace,
+            Token::Ident("x"),
+            Token::Plus,
+            Token::Ident("y"),
+            Token::Semicolon,
+            Token::RBrace,
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("result"),
+            Token::Assign,
+            Token::Ident("add"),
+            Token::LParen,
+            Token::Ident("five"),
+            Token::Comma,
+            Token::Ident("ten"),
+            Token::RParen,
+            Token::Semicolon,
+            Token::Eof,
         ];
-        let mut lexer = Lexer::new(input.to_owned());
+        let mut lexer = Lexer::new(input);
 
         for expected_tok in expected_tokens {
-            let actual_tok = lexer.next_token();
+            let (actual_tok, _span) = lexer.next_token().unwrap();
             println!("actual: {:?}, expected: {:?}", actual_tok, expected_tok);
             assert_eq!(actual_tok, expected_tok);
         }
@@ -279,65 +599,65 @@ mod tests {
         };
 
         let result = add(five, ten);
-        !-/*5;
+        !- / * 5;
         5 < 10 > 5;
         ";
 
         let expected_tokens = vec![
-            Token::LET,
-            Token::IDENT("five".to_owned()),
-            Token::ASSIGN,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("ten".to_owned()),
-            Token::ASSIGN,
-            Token::INT("10".to_owned()),
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("add".to_owned()),
-            Token::ASSIGN,
-            Token::FUNCTION,
-            Token::LPAREN,
-            Token::IDENT("x".to_owned()),
-            Token::COMMA,
-            Token::IDENT("y".to_owned()),
-            Token::RPAREN,
-            Token::LBRACE,
-            Token::IDENT("x".to_owned()),
-            Token::PLUS,
-            Token::IDENT("y".to_owned()),
-            Token::SEMICOLON,
-            Token::RBRACE,
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("result".to_owned()),
-            Token::ASSIGN,
-            Token::IDENT("add".to_owned()),
-            Token::LPAREN,
-            Token::IDENT("five".to_owned()),
-            Token::COMMA,
-            Token::IDENT("ten".to_owned()),
-            Token::RPAREN,
-            Token::SEMICOLON,
-            Token::BANG,
-            Token::MINUS,
-            Token::SLASH,
-            Token::ASTERISK,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::INT("5".to_owned()),
-            Token::LT,
-            Token::INT("10".to_owned()),
-            Token::GT,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::EOF,
+            Token::Let,
+            Token::Ident("five"),
+            Token::Assign,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("ten"),
+            Token::Assign,
+            Token::Int("10"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("add"),
+            Token::Assign,
+            Token::Function,
+            Token::LParen,
+            Token::Ident("x"),
+            Token::Comma,
+            Token::Ident("y"),
+            Token::RParen,
+            Token::LBrace,
+            Token::Ident("x"),
+            Token::Plus,
+            Token::Ident("y"),
+            Token::Semicolon,
+            Token::RBrace,
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("result"),
+            Token::Assign,
+            Token::Ident("add"),
+            Token::LParen,
+            Token::Ident("five"),
+            Token::Comma,
+            Token::Ident("ten"),
+            Token::RParen,
+            Token::Semicolon,
+            Token::Bang,
+            Token::Minus,
+            Token::Slash,
+            Token::Asterisk,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Int("5"),
+            Token::Lt,
+            Token::Int("10"),
+            Token::Gt,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Eof,
         ];
-        let mut lexer = Lexer::new(input.to_owned());
+        let mut lexer = Lexer::new(input);
 
         for expected_tok in expected_tokens {
-            let actual_tok = lexer.next_token();
+            let (actual_tok, _span) = lexer.next_token().unwrap();
             println!("actual: {:?}, expected: {:?}", actual_tok, expected_tok);
             assert_eq!(actual_tok, expected_tok);
         }
@@ -352,7 +672,7 @@ mod tests {
         };
 
         let result = add(five, ten);
-        !-/*5;
+        !- / * 5;
         5 < 10 > 5;
 
         if (5 < 10) {
@@ -366,87 +686,238 @@ mod tests {
         ";
 
         let expected_tokens = vec![
-            Token::LET,
-            Token::IDENT("five".to_owned()),
-            Token::ASSIGN,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("ten".to_owned()),
-            Token::ASSIGN,
-            Token::INT("10".to_owned()),
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("add".to_owned()),
-            Token::ASSIGN,
-            Token::FUNCTION,
-            Token::LPAREN,
-            Token::IDENT("x".to_owned()),
-            Token::COMMA,
-            Token::IDENT("y".to_owned()),
-            Token::RPAREN,
-            Token::LBRACE,
-            Token::IDENT("x".to_owned()),
-            Token::PLUS,
-            Token::IDENT("y".to_owned()),
-            Token::SEMICOLON,
-            Token::RBRACE,
-            Token::SEMICOLON,
-            Token::LET,
-            Token::IDENT("result".to_owned()),
-            Token::ASSIGN,
-            Token::IDENT("add".to_owned()),
-            Token::LPAREN,
-            Token::IDENT("five".to_owned()),
-            Token::COMMA,
-            Token::IDENT("ten".to_owned()),
-            Token::RPAREN,
-            Token::SEMICOLON,
-            Token::BANG,
-            Token::MINUS,
-            Token::SLASH,
-            Token::ASTERISK,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::INT("5".to_owned()),
-            Token::LT,
-            Token::INT("10".to_owned()),
-            Token::GT,
-            Token::INT("5".to_owned()),
-            Token::SEMICOLON,
-            Token::IF,
-            Token::LPAREN,
-            Token::INT("5".to_owned()),
-            Token::LT,
-            Token::INT("10".to_owned()),
-            Token::RPAREN,
-            Token::LBRACE,
-            Token::RETURN,
-            Token::TRUE,
-            Token::SEMICOLON,
-            Token::RBRACE,
-            Token::ELSE,
-            Token::LBRACE,
-            Token::RETURN,
-            Token::FALSE,
-            Token::SEMICOLON,
-            Token::RBRACE,
-            Token::INT("10".to_owned()),
-            Token::EQUAL,
-            Token::INT("10".to_owned()),
-            Token::SEMICOLON,
-            Token::INT("10".to_owned()),
-            Token::NOTEQUAL,
-            Token::INT("9".to_owned()),
-            Token::SEMICOLON,
-            Token::EOF,
+            Token::Let,
+            Token::Ident("five"),
+            Token::Assign,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("ten"),
+            Token::Assign,
+            Token::Int("10"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("add"),
+            Token::Assign,
+            Token::Function,
+            Token::LParen,
+            Token::Ident("x"),
+            Token::Comma,
+            Token::Ident("y"),
+            Token::RParen,
+            Token::LBrace,
+            Token::Ident("x"),
+            Token::Plus,
+            Token::Ident("y"),
+            Token::Semicolon,
+            Token::RBrace,
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("result"),
+            Token::Assign,
+            Token::Ident("add"),
+            Token::LParen,
+            Token::Ident("five"),
+            Token::Comma,
+            Token::Ident("ten"),
+            Token::RParen,
+            Token::Semicolon,
+            Token::Bang,
+            Token::Minus,
+            Token::Slash,
+            Token::Asterisk,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Int("5"),
+            Token::Lt,
+            Token::Int("10"),
+            Token::Gt,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::If,
+            Token::LParen,
+            Token::Int("5"),
+            Token::Lt,
+            Token::Int("10"),
+            Token::RParen,
+            Token::LBrace,
+            Token::Return,
+            Token::True,
+            Token::Semicolon,
+            Token::RBrace,
+            Token::Else,
+            Token::LBrace,
+            Token::Return,
+            Token::False,
+            Token::Semicolon,
+            Token::RBrace,
+            Token::Int("10"),
+            Token::Equal,
+            Token::Int("10"),
+            Token::Semicolon,
+            Token::Int("10"),
+            Token::NotEqual,
+            Token::Int("9"),
+            Token::Semicolon,
+            Token::Eof,
         ];
-        let mut lexer = Lexer::new(input.to_owned());
+        let mut lexer = Lexer::new(input);
 
         for expected_tok in expected_tokens {
-            let actual_tok = lexer.next_token();
+            let (actual_tok, _span) = lexer.next_token().unwrap();
             println!("actual: {:?}, expected: {:?}", actual_tok, expected_tok);
             assert_eq!(actual_tok, expected_tok);
         }
     }
+
+    #[test]
+    fn test_token_spans() {
+        let input = "let x =
+5;";
+        let mut lexer = Lexer::new(input);
+
+        let (tok, span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::Let);
+        assert_eq!(span, Span::new(0, 3, 1, 1));
+
+        let (tok, span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::Ident("x"));
+        assert_eq!(span, Span::new(4, 5, 1, 5));
+
+        let (tok, span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::Assign);
+        assert_eq!(span, Span::new(6, 7, 1, 7));
+
+        let (tok, span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::Int("5"));
+        assert_eq!(span, Span::new(8, 9, 2, 0));
+    }
+
+    #[test]
+    fn test_iterator_and_lex() {
+        let tokens: Vec<Token> = Lexer::new("let x = 5;").map(|res| res.unwrap().0).collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Let,
+                Token::Ident("x"),
+                Token::Assign,
+                Token::Int("5"),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+
+        let tokens: Vec<Token> = lex("1 + 2")
+            .into_iter()
+            .map(|res| res.unwrap().0)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![Token::Int("1"), Token::Plus, Token::Int("2"), Token::Eof,]
+        );
+    }
+
+    #[test]
+    fn test_next_token_error_on_unexpected_character() {
+        let mut lexer = Lexer::new("@");
+        let err = lexer.next_token().unwrap_err();
+        assert_eq!(err.kind, LexErrorKind::UnexpectedCharacter('@'));
+        assert_eq!(err.pos, 0);
+
+        let (tok, _span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::Eof);
+    }
+
+    #[test]
+    fn test_next_token_string() {
+        let mut lexer = Lexer::new("\"hello\" \"foo\\nbar\\t\\\"baz\\\\\"");
+
+        let (tok, _span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::String("hello".to_owned()));
+
+        let (tok, _span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::String("foo\nbar\t\"baz\\".to_owned()));
+    }
+
+    #[test]
+    fn test_next_token_string_utf8() {
+        let mut lexer = Lexer::new("\"héllo\" \"日本語\"");
+
+        let (tok, _span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::String("héllo".to_owned()));
+
+        let (tok, _span) = lexer.next_token().unwrap();
+        assert_eq!(tok, Token::String("日本語".to_owned()));
+    }
+
+    #[test]
+    fn test_next_token_unterminated_string() {
+        let mut lexer = Lexer::new("\"hello");
+        let err = lexer.next_token().unwrap_err();
+        assert_eq!(err.kind, LexErrorKind::UnterminatedString);
+        assert_eq!(err.pos, 0);
+    }
+
+    #[test]
+    fn test_next_token_numbers() {
+        let input = "3.14 0xFF 0b1010 1_000 42";
+        let expected_tokens = vec![
+            Token::Float("3.14"),
+            Token::Int("0xFF"),
+            Token::Int("0b1010"),
+            Token::Int("1_000"),
+            Token::Int("42"),
+        ];
+        let mut lexer = Lexer::new(input);
+
+        for expected_tok in expected_tokens {
+            let (actual_tok, _span) = lexer.next_token().unwrap();
+            assert_eq!(actual_tok, expected_tok);
+        }
+    }
+
+    #[test]
+    fn test_next_token_invalid_numbers() {
+        let mut lexer = Lexer::new("3.");
+        let err = lexer.next_token().unwrap_err();
+        assert_eq!(err.kind, LexErrorKind::InvalidNumber);
+        assert_eq!(err.pos, 0);
+
+        let mut lexer = Lexer::new("0x");
+        let err = lexer.next_token().unwrap_err();
+        assert_eq!(err.kind, LexErrorKind::InvalidNumber);
+        assert_eq!(err.pos, 0);
+    }
+
+    #[test]
+    fn test_next_token_skips_comments() {
+        let input = "// a leading comment
+        let x = 5; /* inline
+        block comment */ let y = 10;";
+
+        let expected_tokens = vec![
+            Token::Let,
+            Token::Ident("x"),
+            Token::Assign,
+            Token::Int("5"),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("y"),
+            Token::Assign,
+            Token::Int("10"),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+        let mut lexer = Lexer::new(input);
+
+        for expected_tok in expected_tokens {
+            let (actual_tok, _span) = lexer.next_token().unwrap();
+            assert_eq!(actual_tok, expected_tok);
+        }
+
+        assert_eq!(lexer.comments().len(), 2);
+        assert_eq!(lexer.comments()[0].1, "// a leading comment");
+    }
 }
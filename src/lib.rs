@@ -0,0 +1,6 @@
+pub mod ast;
+pub mod compiler;
+pub mod lexer;
+pub mod parser;
+pub mod value;
+pub mod vm;
@@ -0,0 +1,564 @@
+//! A Pratt (top-down operator precedence) parser that drives the `Lexer`
+//! and builds the AST defined in the `ast` module.
+
+use std::collections::HashMap;
+
+use crate::ast::{
+    Expression, ExpressionStatement, Identifier, InfixExpression, LetStatement, PrefixExpression,
+    Program, ReturnStatement, Statement,
+};
+use crate::lexer::{Lexer, Span, Token};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnexpectedToken { expected: String, found: String },
+    NoPrefixParseFn(String),
+    InvalidInteger(String),
+    Lex(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// Operator-precedence levels, lowest to highest, used to decide how far an
+/// infix parse should keep folding the left-hand expression.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Precedence {
+    Lowest,
+    Equals,
+    LessGreater,
+    Sum,
+    Product,
+    Prefix,
+    Call,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TokenKind {
+    Ident,
+    Int,
+    Bang,
+    Minus,
+    Plus,
+    Slash,
+    Asterisk,
+    Equal,
+    NotEqual,
+    Lt,
+    Gt,
+}
+
+impl TokenKind {
+    fn of(tok: &Token<'_>) -> Option<Self> {
+        match tok {
+            Token::Ident(_) => Some(TokenKind::Ident),
+            Token::Int(_) => Some(TokenKind::Int),
+            Token::Bang => Some(TokenKind::Bang),
+            Token::Minus => Some(TokenKind::Minus),
+            Token::Plus => Some(TokenKind::Plus),
+            Token::Slash => Some(TokenKind::Slash),
+            Token::Asterisk => Some(TokenKind::Asterisk),
+            Token::Equal => Some(TokenKind::Equal),
+            Token::NotEqual => Some(TokenKind::NotEqual),
+            Token::Lt => Some(TokenKind::Lt),
+            Token::Gt => Some(TokenKind::Gt),
+            _ => None,
+        }
+    }
+
+    fn precedence(self) -> Precedence {
+        match self {
+            TokenKind::Equal | TokenKind::NotEqual => Precedence::Equals,
+            TokenKind::Lt | TokenKind::Gt => Precedence::LessGreater,
+            TokenKind::Plus | TokenKind::Minus => Precedence::Sum,
+            TokenKind::Slash | TokenKind::Asterisk => Precedence::Product,
+            _ => Precedence::Lowest,
+        }
+    }
+}
+
+type PrefixParseFn<'src> = fn(&mut Parser<'src>) -> Option<Expression>;
+type InfixParseFn<'src> = fn(&mut Parser<'src>, Expression) -> Option<Expression>;
+
+pub struct Parser<'src> {
+    lexer: Lexer<'src>,
+
+    cur_token: Token<'src>,
+    cur_span: Span,
+    peek_token: Token<'src>,
+    peek_span: Span,
+
+    errors: Vec<ParseError>,
+
+    prefix_parse_fns: HashMap<TokenKind, PrefixParseFn<'src>>,
+    infix_parse_fns: HashMap<TokenKind, InfixParseFn<'src>>,
+}
+
+impl<'src> Parser<'src> {
+    pub fn new(lexer: Lexer<'src>) -> Self {
+        let mut parser = Self {
+            lexer,
+            cur_token: Token::Eof,
+            cur_span: Span::default(),
+            peek_token: Token::Eof,
+            peek_span: Span::default(),
+            errors: Vec::new(),
+            prefix_parse_fns: HashMap::new(),
+            infix_parse_fns: HashMap::new(),
+        };
+
+        parser.register_prefix(TokenKind::Ident, Parser::parse_identifier);
+        parser.register_prefix(TokenKind::Int, Parser::parse_integer_literal);
+        parser.register_prefix(TokenKind::Bang, Parser::parse_prefix_expression);
+        parser.register_prefix(TokenKind::Minus, Parser::parse_prefix_expression);
+
+        parser.register_infix(TokenKind::Plus, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::Minus, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::Slash, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::Asterisk, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::Equal, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::NotEqual, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::Lt, Parser::parse_infix_expression);
+        parser.register_infix(TokenKind::Gt, Parser::parse_infix_expression);
+
+        // Prime cur_token/peek_token.
+        parser.next_token();
+        parser.next_token();
+
+        parser
+    }
+
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn register_prefix(&mut self, kind: TokenKind, f: PrefixParseFn<'src>) {
+        self.prefix_parse_fns.insert(kind, f);
+    }
+
+    fn register_infix(&mut self, kind: TokenKind, f: InfixParseFn<'src>) {
+        self.infix_parse_fns.insert(kind, f);
+    }
+
+    fn next_token(&mut self) {
+        let (next_tok, next_span) = match self.lexer.next_token() {
+            Ok(pair) => pair,
+            Err(err) => {
+                let span = Span::at(err.pos);
+                self.errors
+                    .push(ParseError::new(ParseErrorKind::Lex(err.to_string()), span));
+                (Token::Illegal, span)
+            }
+        };
+
+        self.cur_token = std::mem::replace(&mut self.peek_token, next_tok);
+        self.cur_span = std::mem::replace(&mut self.peek_span, next_span);
+    }
+
+    fn peek_precedence(&self) -> Precedence {
+        TokenKind::of(&self.peek_token)
+            .map(TokenKind::precedence)
+            .unwrap_or(Precedence::Lowest)
+    }
+
+    fn cur_precedence(&self) -> Precedence {
+        TokenKind::of(&self.cur_token)
+            .map(TokenKind::precedence)
+            .unwrap_or(Precedence::Lowest)
+    }
+
+    fn expect_peek(&mut self, expected: &Token<'_>) -> bool {
+        if std::mem::discriminant(&self.peek_token) == std::mem::discriminant(expected) {
+            self.next_token();
+            true
+        } else {
+            self.errors.push(ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    expected: expected.to_string(),
+                    found: self.peek_token.to_string(),
+                },
+                self.peek_span,
+            ));
+            false
+        }
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        let mut program = Program::default();
+
+        while self.cur_token != Token::Eof {
+            if let Some(statement) = self.parse_statement() {
+                program.statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        program
+    }
+
+    fn parse_statement(&mut self) -> Option<Statement> {
+        match self.cur_token {
+            Token::Let => self.parse_let_statement(),
+            Token::Return => self.parse_return_statement(),
+            _ => self.parse_expression_statement(),
+        }
+    }
+
+    fn parse_let_statement(&mut self) -> Option<Statement> {
+        if !self.expect_peek(&Token::Ident("")) {
+            return None;
+        }
+
+        let name = match &self.cur_token {
+            Token::Ident(value) => Identifier {
+                value: (*value).to_owned(),
+            },
+            _ => unreachable!("expect_peek guarantees an IDENT token"),
+        };
+
+        if !self.expect_peek(&Token::Assign) {
+            return None;
+        }
+
+        self.next_token();
+        let value = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token == Token::Semicolon {
+            self.next_token();
+        }
+
+        Some(Statement::Let(LetStatement { name, value }))
+    }
+
+    fn parse_return_statement(&mut self) -> Option<Statement> {
+        self.next_token();
+        let return_value = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token == Token::Semicolon {
+            self.next_token();
+        }
+
+        Some(Statement::Return(ReturnStatement { return_value }))
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<Statement> {
+        let expression = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token == Token::Semicolon {
+            self.next_token();
+        }
+
+        Some(Statement::Expression(ExpressionStatement { expression }))
+    }
+
+    fn parse_expression(&mut self, precedence: Precedence) -> Option<Expression> {
+        let prefix = TokenKind::of(&self.cur_token).and_then(|k| self.prefix_parse_fns.get(&k));
+
+        let Some(prefix) = prefix else {
+            self.errors.push(ParseError::new(
+                ParseErrorKind::NoPrefixParseFn(self.cur_token.to_string()),
+                self.cur_span,
+            ));
+            return None;
+        };
+
+        let mut left = prefix(self)?;
+
+        while self.peek_token != Token::Semicolon && precedence < self.peek_precedence() {
+            let infix = TokenKind::of(&self.peek_token).and_then(|k| self.infix_parse_fns.get(&k));
+            let Some(infix) = infix else {
+                return Some(left);
+            };
+            let infix = *infix;
+
+            self.next_token();
+            left = infix(self, left)?;
+        }
+
+        Some(left)
+    }
+
+    fn parse_identifier(&mut self) -> Option<Expression> {
+        match &self.cur_token {
+            Token::Ident(value) => Some(Expression::Identifier(Identifier {
+                value: (*value).to_owned(),
+            })),
+            _ => unreachable!("parse_identifier is only registered for IDENT tokens"),
+        }
+    }
+
+    fn parse_integer_literal(&mut self) -> Option<Expression> {
+        match &self.cur_token {
+            Token::Int(raw) => match parse_int_literal(raw) {
+                Ok(value) => Some(Expression::IntegerLiteral(value)),
+                Err(_) => {
+                    self.errors.push(ParseError::new(
+                        ParseErrorKind::InvalidInteger((*raw).to_owned()),
+                        self.cur_span,
+                    ));
+                    None
+                }
+            },
+            _ => unreachable!("parse_integer_literal is only registered for INT tokens"),
+        }
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<Expression> {
+        let operator = self
+            .cur_token
+            .operator_lexeme()
+            .expect("parse_prefix_expression is only registered for operator tokens")
+            .to_owned();
+
+        self.next_token();
+        let right = self.parse_expression(Precedence::Prefix)?;
+
+        Some(Expression::Prefix(PrefixExpression {
+            operator,
+            right: Box::new(right),
+        }))
+    }
+
+    fn parse_infix_expression(&mut self, left: Expression) -> Option<Expression> {
+        let operator = self
+            .cur_token
+            .operator_lexeme()
+            .expect("parse_infix_expression is only registered for operator tokens")
+            .to_owned();
+        let precedence = self.cur_precedence();
+
+        self.next_token();
+        let right = self.parse_expression(precedence)?;
+
+        Some(Expression::Infix(InfixExpression {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }))
+    }
+}
+
+/// Parses an integer literal's raw lexeme, as produced by the lexer: an
+/// optional `0x`/`0b` radix prefix followed by digits, with `_` separators
+/// allowed anywhere in the digit run.
+fn parse_int_literal(raw: &str) -> Result<i64, std::num::ParseIntError> {
+    let (digits, radix) = if let Some(hex) = raw.strip_prefix("0x").or(raw.strip_prefix("0X")) {
+        (hex, 16)
+    } else if let Some(bin) = raw.strip_prefix("0b").or(raw.strip_prefix("0B")) {
+        (bin, 2)
+    } else {
+        (raw, 10)
+    };
+
+    i64::from_str_radix(&digits.replace('_', ""), radix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+
+    fn parse(input: &str) -> Program {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert_eq!(parser.errors(), &[], "parser had errors: {:?}", parser.errors());
+        program
+    }
+
+    #[test]
+    fn test_let_statements() {
+        let program = parse("let x = 5;\nlet y = 10;\nlet foobar = 838383;");
+
+        let expected = vec![
+            Statement::Let(LetStatement {
+                name: Identifier {
+                    value: "x".to_owned(),
+                },
+                value: Expression::IntegerLiteral(5),
+            }),
+            Statement::Let(LetStatement {
+                name: Identifier {
+                    value: "y".to_owned(),
+                },
+                value: Expression::IntegerLiteral(10),
+            }),
+            Statement::Let(LetStatement {
+                name: Identifier {
+                    value: "foobar".to_owned(),
+                },
+                value: Expression::IntegerLiteral(838383),
+            }),
+        ];
+
+        assert_eq!(program.statements, expected);
+    }
+
+    #[test]
+    fn test_integer_literal_radix_and_underscores() {
+        let program = parse("0xFF; 0b1010; 1_000;");
+
+        let expected = vec![
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::IntegerLiteral(255),
+            }),
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::IntegerLiteral(10),
+            }),
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::IntegerLiteral(1000),
+            }),
+        ];
+
+        assert_eq!(program.statements, expected);
+    }
+
+    #[test]
+    fn test_return_statements() {
+        let program = parse("return 5;\nreturn 993322;");
+
+        let expected = vec![
+            Statement::Return(ReturnStatement {
+                return_value: Expression::IntegerLiteral(5),
+            }),
+            Statement::Return(ReturnStatement {
+                return_value: Expression::IntegerLiteral(993322),
+            }),
+        ];
+
+        assert_eq!(program.statements, expected);
+    }
+
+    #[test]
+    fn test_identifier_expression() {
+        let program = parse("foobar;");
+
+        assert_eq!(
+            program.statements,
+            vec![Statement::Expression(ExpressionStatement {
+                expression: Expression::Identifier(Identifier {
+                    value: "foobar".to_owned(),
+                }),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_prefix_expressions() {
+        let program = parse("!5; -15;");
+
+        let expected = vec![
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::Prefix(PrefixExpression {
+                    operator: "!".to_owned(),
+                    right: Box::new(Expression::IntegerLiteral(5)),
+                }),
+            }),
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::Prefix(PrefixExpression {
+                    operator: "-".to_owned(),
+                    right: Box::new(Expression::IntegerLiteral(15)),
+                }),
+            }),
+        ];
+
+        assert_eq!(program.statements, expected);
+    }
+
+    #[test]
+    fn test_infix_expressions() {
+        let program = parse("5 + 5; 5 * 5;");
+
+        let expected = vec![
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::Infix(InfixExpression {
+                    left: Box::new(Expression::IntegerLiteral(5)),
+                    operator: "+".to_owned(),
+                    right: Box::new(Expression::IntegerLiteral(5)),
+                }),
+            }),
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::Infix(InfixExpression {
+                    left: Box::new(Expression::IntegerLiteral(5)),
+                    operator: "*".to_owned(),
+                    right: Box::new(Expression::IntegerLiteral(5)),
+                }),
+            }),
+        ];
+
+        assert_eq!(program.statements, expected);
+    }
+
+    #[test]
+    fn test_operator_precedence() {
+        let program = parse("a + b * c;");
+
+        let expected = Statement::Expression(ExpressionStatement {
+            expression: Expression::Infix(InfixExpression {
+                left: Box::new(Expression::Identifier(Identifier {
+                    value: "a".to_owned(),
+                })),
+                operator: "+".to_owned(),
+                right: Box::new(Expression::Infix(InfixExpression {
+                    left: Box::new(Expression::Identifier(Identifier {
+                        value: "b".to_owned(),
+                    })),
+                    operator: "*".to_owned(),
+                    right: Box::new(Expression::Identifier(Identifier {
+                        value: "c".to_owned(),
+                    })),
+                })),
+            }),
+        });
+
+        assert_eq!(program.statements, vec![expected]);
+    }
+
+    #[test]
+    fn test_lex_error_surfaces_as_parse_error() {
+        let lexer = Lexer::new("@ 5;");
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        assert_eq!(
+            parser.errors(),
+            &[
+                ParseError {
+                    kind: ParseErrorKind::Lex("unexpected character '@' at byte 0".to_owned()),
+                    span: Span::at(0),
+                },
+                ParseError {
+                    kind: ParseErrorKind::NoPrefixParseFn("Illegal".to_owned()),
+                    span: Span::at(0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_let_statement_missing_assign_reports_error() {
+        let lexer = Lexer::new("let x 5;");
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        assert_eq!(
+            parser.errors(),
+            &[ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: "Assign".to_owned(),
+                    found: "Int(5)".to_owned(),
+                },
+                span: Span::new(6, 7, 1, 7),
+            }]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! A stack-based VM that executes the bytecode produced by the `compiler`
+//! module.
+
+use std::fmt::Display;
+
+use crate::compiler::{Chunk, Instruction};
+use crate::lexer::Span;
+use crate::value::Value;
+
+const STACK_MAX: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VmError {
+    InvalidInstruction(u8, Span),
+    StackUnderflow,
+    StackOverflow,
+    DivideByZero,
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::InvalidInstruction(byte, span) => {
+                write!(f, "invalid instruction byte {} at {:?}", byte, span)
+            }
+            VmError::StackUnderflow => write!(f, "stack underflow"),
+            VmError::StackOverflow => write!(f, "stack overflow"),
+            VmError::DivideByZero => write!(f, "divide by zero"),
+        }
+    }
+}
+
+/// Walks a `Chunk`'s bytecode one instruction at a time, maintaining a value
+/// stack and an instruction pointer (`ip`) into `chunk.code`.
+pub struct Vm<'a> {
+    chunk: &'a Chunk,
+    ip: usize,
+    stack: Vec<Value>,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(chunk: &'a Chunk) -> Self {
+        Self {
+            chunk,
+            ip: 0,
+            stack: Vec::new(),
+        }
+    }
+
+    fn read(&mut self) -> (u8, Span) {
+        let pair = self.chunk.code[self.ip];
+        self.ip += 1;
+        pair
+    }
+
+    fn push(&mut self, value: Value) -> Result<(), VmError> {
+        if self.stack.len() >= STACK_MAX {
+            return Err(VmError::StackOverflow);
+        }
+
+        self.stack.push(value);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Value, VmError> {
+        self.stack.pop().ok_or(VmError::StackUnderflow)
+    }
+
+    /// Runs until a `Return` instruction (yielding its value) or the end of
+    /// the chunk (yielding `None`).
+    pub fn interpret(&mut self) -> Result<Option<Value>, VmError> {
+        while self.ip < self.chunk.code.len() {
+            let (byte, span) = self.read();
+            let instruction =
+                Instruction::from_byte(byte).ok_or(VmError::InvalidInstruction(byte, span))?;
+
+            match instruction {
+                Instruction::Constant => {
+                    let (index, _) = self.read();
+                    let value = self.chunk.constants[index as usize];
+                    self.push(value)?;
+                }
+                Instruction::Pop => {
+                    self.pop()?;
+                }
+                Instruction::Return => {
+                    let value = self.pop()?;
+                    return Ok(Some(value));
+                }
+                Instruction::Negate => {
+                    let Value::Int(v) = self.pop()?;
+                    self.push(Value::Int(v.wrapping_neg()))?;
+                }
+                Instruction::Add => self.binary_op(|a, b| Ok(a.wrapping_add(b)))?,
+                Instruction::Subtract => self.binary_op(|a, b| Ok(a.wrapping_sub(b)))?,
+                Instruction::Multiply => self.binary_op(|a, b| Ok(a.wrapping_mul(b)))?,
+                Instruction::Divide => self.binary_op(|a, b| {
+                    if b == 0 {
+                        Err(VmError::DivideByZero)
+                    } else {
+                        Ok(a.wrapping_div(b))
+                    }
+                })?,
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn binary_op(&mut self, f: impl FnOnce(i64, i64) -> Result<i64, VmError>) -> Result<(), VmError> {
+        let Value::Int(b) = self.pop()?;
+        let Value::Int(a) = self.pop()?;
+        self.push(Value::Int(f(a, b)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+
+    fn run(input: &str) -> Option<Value> {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert_eq!(parser.errors(), &[]);
+        let chunk = compile(&program).expect("compile failed");
+        Vm::new(&chunk).interpret().expect("interpret failed")
+    }
+
+    #[test]
+    fn test_interpret_integer_literal() {
+        assert_eq!(run("5;"), Some(Value::Int(5)));
+    }
+
+    #[test]
+    fn test_interpret_arithmetic() {
+        assert_eq!(run("1 + 2 * 3;"), Some(Value::Int(7)));
+        assert_eq!(run("-5 + 10;"), Some(Value::Int(5)));
+    }
+
+    #[test]
+    fn test_interpret_negate_min_int_does_not_panic() {
+        let mut chunk = Chunk::default();
+        chunk.constants.push(Value::Int(i64::MIN));
+        chunk.code.push((Instruction::Constant as u8, Span::default()));
+        chunk.code.push((0, Span::default()));
+        chunk.code.push((Instruction::Negate as u8, Span::default()));
+        chunk.code.push((Instruction::Return as u8, Span::default()));
+
+        assert_eq!(
+            Vm::new(&chunk).interpret(),
+            Ok(Some(Value::Int(i64::MIN)))
+        );
+    }
+
+    #[test]
+    fn test_interpret_divide_by_zero_is_an_error() {
+        let lexer = Lexer::new("1 / 0;");
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert_eq!(parser.errors(), &[]);
+        let chunk = compile(&program).expect("compile failed");
+        let err = Vm::new(&chunk).interpret().unwrap_err();
+        assert_eq!(err, VmError::DivideByZero);
+    }
+
+    #[test]
+    fn test_interpret_invalid_instruction() {
+        let mut chunk = Chunk::default();
+        chunk.code.push((255, Span::default()));
+        let err = Vm::new(&chunk).interpret().unwrap_err();
+        assert_eq!(err, VmError::InvalidInstruction(255, Span::default()));
+    }
+
+    #[test]
+    fn test_interpret_stack_underflow() {
+        let mut chunk = Chunk::default();
+        chunk.code.push((Instruction::Return as u8, Span::default()));
+        let err = Vm::new(&chunk).interpret().unwrap_err();
+        assert_eq!(err, VmError::StackUnderflow);
+    }
+}